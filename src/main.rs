@@ -1,200 +1,783 @@
-use clap::{error, Error, Parser};
+use clap::{error, Error, Parser, ValueEnum};
 use convert_case::{Case, Casing};
-use std::env;
+use rayon::prelude::*;
+use std::io::{Read, Write};
 use std::panic;
-use std::{fs, path::PathBuf};
-use webidl2wit::{ConversionOptions, HandleUnsupported};
+use std::{env, fs, io, path::PathBuf};
+use webidl2wit::{ConversionOptions, HandleUnsupported, PackageName, ResourceInheritance};
+
+/// Mirrors `webidl2wit::HandleUnsupported` so it can be driven from clap.
+#[derive(Clone, Copy, ValueEnum)]
+enum UnsupportedFeaturesArg {
+  Skip,
+  Bail,
+  Warn,
+}
+
+impl From<UnsupportedFeaturesArg> for HandleUnsupported {
+  fn from(value: UnsupportedFeaturesArg) -> Self {
+    match value {
+      UnsupportedFeaturesArg::Skip => HandleUnsupported::Skip,
+      UnsupportedFeaturesArg::Bail => HandleUnsupported::Bail,
+      UnsupportedFeaturesArg::Warn => HandleUnsupported::Warn,
+    }
+  }
+}
+
+/// Mirrors `webidl2wit::ResourceInheritance` so it can be driven from clap.
+#[derive(Clone, Copy, ValueEnum)]
+enum ResourceInheritanceArg {
+  AsMethods,
+  DuplicateMethods,
+  Both,
+}
+
+impl From<ResourceInheritanceArg> for ResourceInheritance {
+  fn from(value: ResourceInheritanceArg) -> Self {
+    match value {
+      ResourceInheritanceArg::AsMethods => ResourceInheritance::AsMethods,
+      ResourceInheritanceArg::DuplicateMethods => ResourceInheritance::DuplicateMethods,
+      ResourceInheritanceArg::Both => ResourceInheritance::Both,
+    }
+  }
+}
+
+/// Parses the CLI's `ns:name@ver` package id syntax into a `PackageName`.
+/// The version is optional, matching the WIT package-id grammar.
+fn parse_package_name(value: &str) -> Result<PackageName, String> {
+  let (namespace, rest) = value
+    .split_once(':')
+    .ok_or_else(|| format!("`{}` is missing the `ns:` prefix, expected `ns:name@ver`", value))?;
+  let (name, version) = match rest.split_once('@') {
+    Some((name, version)) => (
+      name,
+      Some(
+        version
+          .parse()
+          .map_err(|e| format!("`{}` is not a valid version: {}", version, e))?,
+      ),
+    ),
+    None => (rest, None),
+  };
+  Ok(PackageName::new(namespace, name.to_string(), version))
+}
+
+/// Converts a mixin member into the equivalent interface member, so an
+/// `includes` statement can fold a mixin's members onto its target
+/// interface. Every `MixinMember` variant has an `InterfaceMember`
+/// equivalent, so this is a lossless reshaping rather than a lossy filter.
+fn mixin_member_to_interface_member(
+  member: weedle::mixin::MixinMember,
+) -> weedle::interface::InterfaceMember {
+  use weedle::interface::{
+    AttributeInterfaceMember, InterfaceMember, OperationInterfaceMember, StringifierOrInheritOrStatic,
+    StringifierOrStatic,
+  };
+  use weedle::mixin::MixinMember;
+
+  match member {
+    MixinMember::Const(const_member) => InterfaceMember::Const(const_member),
+    MixinMember::Stringifier(stringifier_member) => InterfaceMember::Stringifier(stringifier_member),
+    MixinMember::Attribute(attribute) => InterfaceMember::Attribute(AttributeInterfaceMember {
+      attributes: attribute.attributes,
+      modifier: attribute
+        .stringifier
+        .map(StringifierOrInheritOrStatic::Stringifier),
+      readonly: attribute.readonly,
+      attribute: attribute.attribute,
+      type_: attribute.type_,
+      identifier: attribute.identifier,
+      semi_colon: attribute.semi_colon,
+    }),
+    MixinMember::Operation(operation) => InterfaceMember::Operation(OperationInterfaceMember {
+      attributes: operation.attributes,
+      modifier: operation.stringifier.map(StringifierOrStatic::Stringifier),
+      special: None,
+      return_type: operation.return_type,
+      identifier: operation.identifier,
+      args: operation.args,
+      semi_colon: operation.semi_colon,
+    }),
+  }
+}
+
+/// Fold `partial interface`/`partial dictionary`/`partial namespace`
+/// definitions and `includes` mixin statements into their primary
+/// definition, so a WebIDL spec split across many files converts as if it
+/// were written in one. Partials are resolved before `includes` so that
+/// mixin members copied onto an interface aren't dropped by a later partial
+/// that only sees the original member list.
+fn merge_partials_and_mixins(
+  definitions: Vec<weedle::Definition>,
+  unsupported: UnsupportedFeaturesArg,
+) -> Result<Vec<weedle::Definition>, Error> {
+  use weedle::Definition;
+
+  let mut primaries = Vec::with_capacity(definitions.len());
+  let mut partial_interfaces = Vec::new();
+  let mut partial_dictionaries = Vec::new();
+  let mut partial_namespaces = Vec::new();
+  let mut includes = Vec::new();
+  for definition in definitions {
+    match definition {
+      Definition::PartialInterface(partial) => partial_interfaces.push(partial),
+      Definition::PartialDictionary(partial) => partial_dictionaries.push(partial),
+      Definition::PartialNamespace(partial) => partial_namespaces.push(partial),
+      Definition::IncludesStatement(includes_stmt) => includes.push(includes_stmt),
+      other => primaries.push(other),
+    }
+  }
+
+  let on_unknown_base = |kind: &str, name: &str| -> Result<(), Error> {
+    match unsupported {
+      UnsupportedFeaturesArg::Bail => Err(Error::raw(
+        error::ErrorKind::Io,
+        format!("{} names an unknown base `{}`", kind, name),
+      )),
+      UnsupportedFeaturesArg::Warn => {
+        eprintln!(
+          "Warning: {} names an unknown base `{}`, skipping",
+          kind, name
+        );
+        Ok(())
+      }
+      UnsupportedFeaturesArg::Skip => Ok(()),
+    }
+  };
+
+  for partial in partial_interfaces {
+    let name = partial.identifier.0;
+    let target = primaries.iter_mut().find_map(|definition| match definition {
+      Definition::Interface(interface) if interface.identifier.0 == name => Some(interface),
+      _ => None,
+    });
+    match target {
+      Some(interface) => interface.members.body.extend(partial.members.body),
+      None => on_unknown_base("partial interface", name)?,
+    }
+  }
+  for partial in partial_dictionaries {
+    let name = partial.identifier.0;
+    let target = primaries.iter_mut().find_map(|definition| match definition {
+      Definition::Dictionary(dictionary) if dictionary.identifier.0 == name => Some(dictionary),
+      _ => None,
+    });
+    match target {
+      Some(dictionary) => dictionary.members.body.extend(partial.members.body),
+      None => on_unknown_base("partial dictionary", name)?,
+    }
+  }
+  for partial in partial_namespaces {
+    let name = partial.identifier.0;
+    let target = primaries.iter_mut().find_map(|definition| match definition {
+      Definition::Namespace(namespace) if namespace.identifier.0 == name => Some(namespace),
+      _ => None,
+    });
+    match target {
+      Some(namespace) => namespace.members.body.extend(partial.members.body),
+      None => on_unknown_base("partial namespace", name)?,
+    }
+  }
+
+  // `includes` is resolved after partials. Only the members written in the
+  // primary `interface mixin` are copied: `partial interface mixin`
+  // definitions are not merged and pass through unchanged.
+  for include in includes {
+    let mixin_name = include.rhs_identifier.0;
+    let mixin_members = primaries.iter().find_map(|definition| match definition {
+      Definition::InterfaceMixin(mixin) if mixin.identifier.0 == mixin_name => {
+        Some(mixin.members.body.clone())
+      }
+      _ => None,
+    });
+    let mixin_members = match mixin_members {
+      Some(members) => members,
+      None => {
+        on_unknown_base("includes", mixin_name)?;
+        continue;
+      }
+    };
+    let target_name = include.lhs_identifier.0;
+    let target = primaries.iter_mut().find_map(|definition| match definition {
+      Definition::Interface(interface) if interface.identifier.0 == target_name => {
+        Some(interface)
+      }
+      _ => None,
+    });
+    match target {
+      Some(interface) => interface.members.body.extend(
+        mixin_members
+          .into_iter()
+          .map(mixin_member_to_interface_member),
+      ),
+      None => on_unknown_base("includes", target_name)?,
+    }
+  }
+
+  Ok(primaries)
+}
+
+/// The conventional sentinel meaning "read from stdin" / "write to stdout".
+const STDIO_SENTINEL: &str = "-";
+
+fn is_stdio(path: &PathBuf) -> bool {
+  path.as_os_str() == STDIO_SENTINEL
+}
+
+fn read_input(path: &PathBuf) -> io::Result<String> {
+  if is_stdio(path) {
+    let mut buf = String::new();
+    io::stdin().read_to_string(&mut buf)?;
+    Ok(buf)
+  } else {
+    fs::read_to_string(path)
+  }
+}
+
+fn write_output(path: &PathBuf, contents: &str) -> io::Result<()> {
+  if is_stdio(path) {
+    io::stdout().write_all(contents.as_bytes())
+  } else {
+    fs::write(path, contents)
+  }
+}
 
 /// Search for a pattern in a file and display the lines that contain it.
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 struct Cli {
-  /// The pattern to look for
-  input_idl_path: std::path::PathBuf,
-  /// The path to the file to read
-  output_wit_path: std::path::PathBuf,
+  /// The WebIDL file(s) to convert, merged into a single WIT package. Pass
+  /// `-` (or nothing) to read a single document from stdin.
+  input_idl_path: Vec<PathBuf>,
+  /// The file to write the merged WIT package to, or `-` for stdout, e.g.
+  /// `webidl2wit - -o -` to pipe a single document end to end. This is a
+  /// flag rather than a second positional because `input_idl_path` is a
+  /// greedy `Vec`, which clap only allows before a *required* trailing
+  /// positional — incompatible with output being optional here.
+  #[arg(short = 'o', long = "output-file", conflicts_with = "output_dir")]
+  output_wit_path: Option<PathBuf>,
+  /// A directory to write the merged WIT package into; for directory input,
+  /// one `.wit` file per input file is written here instead
+  #[arg(short = 'd', long = "output-dir", conflicts_with = "output_wit_path")]
+  output_dir: Option<PathBuf>,
+  /// How to handle WebIDL constructs that have no WIT equivalent
+  #[arg(long, value_enum, default_value_t = UnsupportedFeaturesArg::Skip)]
+  unsupported: UnsupportedFeaturesArg,
+  /// Collapse every loose definition into one synthetic interface whose
+  /// name is prefixed with this value, instead of erroring on definitions
+  /// that aren't inside a WebIDL `interface`/`namespace`
+  #[arg(long)]
+  singleton_interface: Option<String>,
+  /// How to convert WebIDL interface inheritance into WIT resources
+  #[arg(long, value_enum)]
+  resource_inheritance: Option<ResourceInheritanceArg>,
+  /// Override the auto-derived (kebab-cased) interface name
+  #[arg(long)]
+  interface_name: Option<String>,
+  /// The WIT package id to emit, e.g. `ns:name@1.0.0`
+  #[arg(long, value_name = "ns:name@ver", value_parser = parse_package_name)]
+  package_name: Option<PackageName>,
+  /// Search these directories for base WebIDL definitions that `partial`
+  /// and `includes` statements in the input files extend
+  #[arg(short = 'I', long = "include-dir")]
+  include_dir: Vec<PathBuf>,
 }
 
-fn convert_file(input_file: &PathBuf, output_file: &PathBuf) -> Result<(), Error> {
-  println!(
-    "Converting file: {} -> {}",
-    input_file.display(),
-    output_file.display()
-  );
-  let webidl_input = match fs::read_to_string(&input_file) {
-    Ok(s) => s,
-    Err(e) => {
-      return Err(Error::raw(
-        error::ErrorKind::Io,
-        format!("Error reading input file: {}", e),
-      ));
+/// The interface name to fall back to when `--interface-name` isn't given:
+/// derived from the first input file's name, or the stdio fallback when
+/// that file is actually stdin.
+fn default_interface_name_for(input_files: &[PathBuf]) -> String {
+  if is_stdio(&input_files[0]) {
+    "stdin-interface".to_string()
+  } else {
+    interface_name_for(&input_files[0])
+  }
+}
+
+fn interface_name_for(path: &PathBuf) -> String {
+  format!(
+    "{}-interface",
+    path
+      .file_stem()
+      .unwrap()
+      .to_string_lossy()
+      .to_string()
+      .chars()
+      .filter(|c| !c.is_numeric())
+      .collect::<String>()
+      .to_case(Case::Kebab)
+  )
+}
+
+/// Parse and convert already-loaded WebIDL sources into a WIT document.
+///
+/// This is the core conversion step, factored out of file/stdio handling so
+/// it can be driven from a file, stdin, or (in future) in-memory tests alike.
+fn convert_sources(
+  labels: &[String],
+  sources: &[String],
+  default_interface_name: String,
+  singleton_default: bool,
+  cli: &Cli,
+) -> Result<Option<String>, Error> {
+  panic::catch_unwind(|| {
+    let mut webidl_ast = Vec::new();
+    for (label, webidl_input) in labels.iter().zip(sources) {
+      match weedle::parse(webidl_input) {
+        Ok(mut ast) => webidl_ast.append(&mut ast),
+        Err(e) => {
+          return Err(Error::raw(
+            error::ErrorKind::Io,
+            format!("Error parsing input file {}: {}", label, e),
+          ));
+        }
+      }
     }
-  };
-  // Convert
-  let result = panic::catch_unwind(|| {
-    // Set Conversion Options
-    let convert_options = ConversionOptions {
-      interface_name: format!(
-        "{}-interface",
-        input_file
-          .file_stem()
-          .unwrap()
-          .to_string_lossy()
-          .to_string()
-          .chars()
-          .filter(|c| !c.is_numeric())
-          .collect::<String>()
-          .to_case(Case::Kebab)
-      ),
-      singleton_interface: Some("global-".to_string()),
-      // resource_inheritance: ResourceInheritance::DuplicateMethods,
-      unsupported_features: HandleUnsupported::Skip,
+    // Resolve `partial interface`/`dictionary`/`namespace` and `includes`
+    // across every merged file before conversion, so a spec split across
+    // files converts as if it were written as one.
+    let webidl_ast = merge_partials_and_mixins(webidl_ast, cli.unsupported)?;
+    // Set Conversion Options. With a single input file we fall back to a
+    // singleton interface (as before); with several files we let each
+    // file's own `interface`/`namespace` definitions become their own WIT
+    // interface so cross-file type references still resolve within the
+    // one merged package. Either default can be overridden from the CLI.
+    let mut convert_options = ConversionOptions {
+      interface_name: cli
+        .interface_name
+        .clone()
+        .unwrap_or(default_interface_name),
+      singleton_interface: cli.singleton_interface.clone().or_else(|| {
+        if singleton_default {
+          Some("global-".to_string())
+        } else {
+          None
+        }
+      }),
+      unsupported_features: cli.unsupported.into(),
+      package_name: cli
+        .package_name
+        .clone()
+        .unwrap_or_else(|| ConversionOptions::default().package_name),
       ..Default::default()
     };
-    let webidl_ast = match weedle::parse(&webidl_input) {
-      Ok(ast) => ast,
-      Err(e) => {
-        return Err(Error::raw(
-          error::ErrorKind::Io,
-          format!("Error parsing input file: {}", e),
-        ));
-      }
-    };
+    if let Some(resource_inheritance) = cli.resource_inheritance {
+      convert_options.resource_inheritance = resource_inheritance.into();
+    }
     let wit_ast = match webidl2wit::webidl_to_wit(webidl_ast, convert_options) {
       Ok(ast) => ast,
       Err(e) => {
-        println!("Error converting webidl to wit: {}", e);
+        eprintln!("Error converting webidl to wit: {}", e);
         // Non fatal
         return Ok(None);
       }
     };
-    let wit_output = wit_ast.to_string();
-    return Ok(Some(wit_output));
-  });
-  let wit_output = match result {
-    Ok(Ok(Some(s))) => s,
-    Ok(Err(e)) => {
-      return Err(e);
-    }
-    Err(_) | Ok(Ok(None)) => {
-      // TODO: non fatal error
-      return Ok(());
-    }
-  };
-  // Write Output File
-  match fs::write(&output_file, wit_output) {
-    Ok(_) => (),
-    Err(e) => {
-      return Err(Error::raw(
-        error::ErrorKind::Io,
-        format!("Error writing output file: {}", e),
-      ));
-    }
-  };
-  Ok(())
+    Ok(Some(wit_ast.to_string()))
+  })
+  .unwrap_or_else(|panic| {
+    // A genuine panic (e.g. malformed input the parser can't recover from)
+    // is a hard failure, not an `--unsupported`-driven skip: surface it as
+    // an `Err` so callers report it as failed instead of silently skipped.
+    Err(Error::raw(
+      error::ErrorKind::Io,
+      format!("Panicked while converting: {}", panic_message(&panic)),
+    ))
+  })
 }
 
-fn convert_directory(
-  base_dir: &PathBuf,
-  input_dir: &PathBuf,
-  output_dir: &PathBuf,
-) -> Result<(), Error> {
-  println!(
-    "Converting directory: {} -> {}",
-    input_dir.display(),
-    output_dir.display()
-  );
-  for entry in fs::read_dir(&input_dir)? {
-    let path = match entry {
-      Ok(e) => e.path(),
+/// Extracts a human-readable message from a `catch_unwind` payload.
+fn panic_message(panic: &Box<dyn std::any::Any + Send>) -> String {
+  if let Some(message) = panic.downcast_ref::<&str>() {
+    message.to_string()
+  } else if let Some(message) = panic.downcast_ref::<String>() {
+    message.clone()
+  } else {
+    "unknown panic".to_string()
+  }
+}
+
+/// Reads and converts `input_files` into a WIT document, without writing it
+/// anywhere. Returns `Ok(None)` if the conversion was skipped (a non-fatal
+/// conversion error, per `--unsupported`), or `Err` on a hard IO/parse
+/// failure.
+fn convert_to_string(input_files: &[PathBuf], cli: &Cli) -> Result<Option<String>, Error> {
+  let mut labels = Vec::new();
+  let mut sources = Vec::new();
+  for include_dir in &cli.include_dir {
+    for path in collect_idl_files(include_dir)? {
+      let webidl_input = fs::read_to_string(&path).map_err(|e| {
+        Error::raw(
+          error::ErrorKind::Io,
+          format!("Error reading include-dir file {}: {}", path.display(), e),
+        )
+      })?;
+      labels.push(path.display().to_string());
+      sources.push(webidl_input);
+    }
+  }
+  for input_file in input_files {
+    let webidl_input = match read_input(input_file) {
+      Ok(s) => s,
       Err(e) => {
         return Err(Error::raw(
           error::ErrorKind::Io,
-          format!("Error reading directory: {}", e),
+          format!("Error reading input file {}: {}", input_file.display(), e),
         ));
       }
     };
-    let relative_path = match path.strip_prefix(base_dir) {
-      Ok(p) => p,
+    labels.push(if is_stdio(input_file) {
+      "<stdin>".to_string()
+    } else {
+      input_file.display().to_string()
+    });
+    sources.push(webidl_input);
+  }
+  let default_interface_name = default_interface_name_for(input_files);
+  let singleton_default = input_files.len() == 1;
+  let converted = convert_sources(
+    &labels,
+    &sources,
+    default_interface_name,
+    singleton_default,
+    cli,
+  )?;
+  // Under `--unsupported bail` a conversion error is fatal, not a skip.
+  if converted.is_none() && matches!(cli.unsupported, UnsupportedFeaturesArg::Bail) {
+    return Err(Error::raw(
+      error::ErrorKind::Io,
+      "Conversion failed under `--unsupported bail`",
+    ));
+  }
+  Ok(converted)
+}
+
+/// Converts `input_files` into `output_file`. Returns `Ok(true)` if a WIT
+/// file was written, `Ok(false)` if the conversion was skipped (a non-fatal
+/// conversion error, per `--unsupported`), or `Err` on a hard IO/parse
+/// failure.
+fn convert_files(input_files: &[PathBuf], output_file: &PathBuf, cli: &Cli) -> Result<bool, Error> {
+  eprintln!(
+    "Converting {} file(s) -> {}",
+    input_files.len(),
+    output_file.display()
+  );
+  let wit_output = match convert_to_string(input_files, cli)? {
+    Some(s) => s,
+    None => return Ok(false),
+  };
+  write_wit_output(output_file, &wit_output)?;
+  Ok(true)
+}
+
+fn write_wit_output(output_file: &PathBuf, wit_output: &str) -> Result<(), Error> {
+  if let Some(parent) = output_file.parent() {
+    if !is_stdio(output_file) {
+      fs::create_dir_all(parent).map_err(|e| {
+        Error::raw(
+          error::ErrorKind::Io,
+          format!("Error creating output directory {}: {}", parent.display(), e),
+        )
+      })?;
+    }
+  }
+  write_output(output_file, wit_output).map_err(|e| {
+    Error::raw(
+      error::ErrorKind::Io,
+      format!("Error writing output file: {}", e),
+    )
+  })
+}
+
+/// The outcome of converting every `.idl`/`.webidl` file under a directory.
+#[derive(Default)]
+struct DirectoryReport {
+  converted: usize,
+  skipped: usize,
+  failed: Vec<(PathBuf, Error)>,
+}
+
+impl DirectoryReport {
+  fn record(&mut self, path: PathBuf, outcome: Result<bool, Error>) {
+    match outcome {
+      Ok(true) => self.converted += 1,
+      Ok(false) => self.skipped += 1,
+      Err(e) => self.failed.push((path, e)),
+    }
+  }
+
+  fn merge(&mut self, other: DirectoryReport) {
+    self.converted += other.converted;
+    self.skipped += other.skipped;
+    self.failed.extend(other.failed);
+  }
+
+  fn print_summary(&self) {
+    println!(
+      "{} converted, {} skipped, {} failed",
+      self.converted,
+      self.skipped,
+      self.failed.len()
+    );
+    for (path, error) in &self.failed {
+      println!("  {}: {}", path.display(), error);
+    }
+  }
+}
+
+/// Recursively collects every `.idl`/`.webidl` file under `input_dir`.
+fn collect_idl_files(input_dir: &PathBuf) -> Result<Vec<PathBuf>, Error> {
+  let mut files = Vec::new();
+  for entry in fs::read_dir(input_dir)? {
+    let path = match entry {
+      Ok(e) => e.path(),
       Err(e) => {
         return Err(Error::raw(
           error::ErrorKind::Io,
-          format!("Error stripping prefix: {}", e),
+          format!("Error reading directory: {}", e),
         ));
       }
     };
-    match path.is_dir() {
-      true => {
-        let output_path = output_dir.join(relative_path);
-        match convert_directory(&base_dir, &path, &output_path) {
-          Err(e) => return Err(e),
-          Ok(_) => (),
-        }
-      }
-      false => {
-        if path
-          .extension()
-          .map_or(true, |x| x != "idl" && x != "webidl")
-        {
-          continue;
-        }
-        let file_name = match path.file_name() {
-          None => {
-            return Err(Error::raw(
-              error::ErrorKind::Io,
-              format!("Error reading file name: {}", path.display()),
-            ));
-          }
-          Some(f) => f,
-        };
-        let output_file = output_dir.join(file_name).with_extension("wit");
-        match convert_file(&path, &output_file) {
-          Err(e) => return Err(e),
-          Ok(_) => (),
-        }
-      }
+    if path.is_dir() {
+      files.extend(collect_idl_files(&path)?);
+    } else if path
+      .extension()
+      .is_some_and(|x| x == "idl" || x == "webidl")
+    {
+      files.push(path);
+    }
+  }
+  Ok(files)
+}
+
+fn convert_directory(
+  base_dir: &PathBuf,
+  input_dir: &PathBuf,
+  output_dir: &PathBuf,
+  cli: &Cli,
+) -> Result<DirectoryReport, Error> {
+  eprintln!(
+    "Converting directory: {} -> {}",
+    input_dir.display(),
+    output_dir.display()
+  );
+  let idl_files = collect_idl_files(input_dir)?;
+  // Each file is independent (webidl_to_wit is wrapped in catch_unwind), so
+  // convert them concurrently and only write the outputs afterwards.
+  let outcomes: Vec<(PathBuf, PathBuf, Result<Option<String>, Error>)> = idl_files
+    .par_iter()
+    .map(|path| {
+      let relative_path = path.strip_prefix(base_dir).unwrap_or(path);
+      let output_file = output_dir.join(relative_path).with_extension("wit");
+      let outcome = convert_to_string(&[path.clone()], cli);
+      (path.clone(), output_file, outcome)
+    })
+    .collect();
+  let mut report = DirectoryReport::default();
+  for (path, output_file, outcome) in outcomes {
+    let outcome = match outcome {
+      Ok(Some(wit_output)) => write_wit_output(&output_file, &wit_output).map(|_| true),
+      Ok(None) => Ok(false),
+      Err(e) => Err(e),
     };
+    report.record(path, outcome);
   }
-  Ok(())
+  Ok(report)
 }
 
 fn main() -> Result<(), Error> {
   env::set_var("RUST_BACKTRACE", "1");
-  let args = Cli::parse();
-  // Read Input File
-  #[warn(unused_parens)]
-  let result = match (args.input_idl_path.is_dir(), args.output_wit_path.is_dir()) {
-    (true, false) => Err(Error::raw(
+  let mut args = Cli::parse();
+  if args.input_idl_path.is_empty() {
+    // No input given: read a single WebIDL document from stdin.
+    args.input_idl_path.push(PathBuf::from(STDIO_SENTINEL));
+  }
+  // Read Input File(s)
+  let all_dirs = args.input_idl_path.iter().all(|p| p.is_dir());
+  let any_dirs = args.input_idl_path.iter().any(|p| p.is_dir());
+  let result = match (any_dirs, args.output_dir.clone()) {
+    (true, _) if !all_dirs => Err(Error::raw(
+      error::ErrorKind::Io,
+      "Cannot mix directories and files as input",
+    )),
+    (true, None) => Err(Error::raw(
       error::ErrorKind::Io,
-      "Cannot output directory to file",
+      "Converting a directory requires --output-dir",
     )),
-    (false, false) => convert_file(&args.input_idl_path, &args.output_wit_path),
-    (false, true) => {
-      let file_name = match args.input_idl_path.file_name() {
-        None => {
+    (true, Some(output_dir)) => {
+      let mut report = DirectoryReport::default();
+      for input_dir in &args.input_idl_path {
+        report.merge(convert_directory(input_dir, input_dir, &output_dir, &args)?);
+      }
+      report.print_summary();
+      if !report.failed.is_empty() {
+        std::process::exit(1);
+      }
+      return Ok(());
+    }
+    (false, output_dir) => {
+      let output_file = match (args.output_wit_path.clone(), output_dir) {
+        (Some(_), Some(_)) => unreachable!("clap enforces --output-file/--output-dir exclusivity"),
+        (Some(output_file), None) => output_file,
+        (None, Some(output_dir)) => output_dir
+          .join(default_interface_name_for(&args.input_idl_path))
+          .with_extension("wit"),
+        (None, None) => {
           return Err(Error::raw(
-            error::ErrorKind::Io,
-            format!("Error reading file name: {}", args.input_idl_path.display()),
+            error::ErrorKind::MissingRequiredArgument,
+            "Either --output-file or --output-dir is required",
           ));
         }
-        Some(f) => f,
       };
-      let output_file = &args.output_wit_path.join(file_name).with_extension("wit");
-      convert_file(&args.input_idl_path, &output_file)
+      convert_files(&args.input_idl_path, &output_file, &args).map(|_| ())
     }
-    (true, true) => convert_directory(
-      &args.input_idl_path,
-      &args.input_idl_path,
-      &args.output_wit_path,
-    ),
   };
 
   // End With Ok
   match result {
     Ok(_) => {
-      println!("Conversion successful");
+      eprintln!("Conversion successful");
       Ok(())
     }
     Err(e) => e.exit(),
   }
 }
+
+#[cfg(test)]
+mod merge_partials_and_mixins_tests {
+  use super::*;
+  use weedle::namespace::NamespaceMember;
+  use weedle::Definition;
+
+  fn parse_all(sources: &[&'static str]) -> Vec<Definition<'static>> {
+    let mut definitions = Vec::new();
+    for source in sources {
+      definitions.append(&mut weedle::parse(source).expect("valid webidl"));
+    }
+    definitions
+  }
+
+  fn find_interface<'a>(
+    definitions: &'a [Definition<'static>],
+    name: &str,
+  ) -> &'a weedle::InterfaceDefinition<'static> {
+    definitions
+      .iter()
+      .find_map(|d| match d {
+        Definition::Interface(interface) if interface.identifier.0 == name => Some(interface),
+        _ => None,
+      })
+      .unwrap_or_else(|| panic!("no interface named `{}`", name))
+  }
+
+  fn attribute_identifier<'a>(member: &'a weedle::interface::InterfaceMember<'a>) -> &'a str {
+    match member {
+      weedle::interface::InterfaceMember::Attribute(attribute) => attribute.identifier.0,
+      _ => panic!("expected an attribute member"),
+    }
+  }
+
+  #[test]
+  fn merges_partial_interface_members_in_order() {
+    let definitions = parse_all(&[
+      "interface Foo { attribute long a; };",
+      "partial interface Foo { attribute long b; };",
+    ]);
+    let merged = merge_partials_and_mixins(definitions, UnsupportedFeaturesArg::Bail).unwrap();
+    let interface = find_interface(&merged, "Foo");
+    let names: Vec<&str> = interface
+      .members
+      .body
+      .iter()
+      .map(attribute_identifier)
+      .collect();
+    assert_eq!(names, vec!["a", "b"]);
+  }
+
+  #[test]
+  fn merges_partial_dictionary_members_in_order() {
+    let definitions = parse_all(&[
+      "dictionary Foo { long a; };",
+      "partial dictionary Foo { long b; };",
+    ]);
+    let merged = merge_partials_and_mixins(definitions, UnsupportedFeaturesArg::Bail).unwrap();
+    let dictionary = merged
+      .iter()
+      .find_map(|d| match d {
+        Definition::Dictionary(dictionary) if dictionary.identifier.0 == "Foo" => Some(dictionary),
+        _ => None,
+      })
+      .expect("merged dictionary Foo");
+    let names: Vec<&str> = dictionary
+      .members
+      .body
+      .iter()
+      .map(|member| member.identifier.0)
+      .collect();
+    assert_eq!(names, vec!["a", "b"]);
+  }
+
+  #[test]
+  fn merges_partial_namespace_members_in_order() {
+    let definitions = parse_all(&[
+      "namespace Foo { long a(); };",
+      "partial namespace Foo { long b(); };",
+    ]);
+    let merged = merge_partials_and_mixins(definitions, UnsupportedFeaturesArg::Bail).unwrap();
+    let namespace = merged
+      .iter()
+      .find_map(|d| match d {
+        Definition::Namespace(namespace) if namespace.identifier.0 == "Foo" => Some(namespace),
+        _ => None,
+      })
+      .expect("merged namespace Foo");
+    let names: Vec<&str> = namespace
+      .members
+      .body
+      .iter()
+      .map(|member| match member {
+        NamespaceMember::Operation(operation) => operation.identifier.unwrap().0,
+        _ => panic!("expected an operation member"),
+      })
+      .collect();
+    assert_eq!(names, vec!["a", "b"]);
+  }
+
+  #[test]
+  fn merges_includes_mixin_members_onto_interface() {
+    let definitions = parse_all(&[
+      "interface Foo {};",
+      "interface mixin Bar { attribute long x; };",
+      "Foo includes Bar;",
+    ]);
+    let merged = merge_partials_and_mixins(definitions, UnsupportedFeaturesArg::Bail).unwrap();
+    let interface = find_interface(&merged, "Foo");
+    let names: Vec<&str> = interface
+      .members
+      .body
+      .iter()
+      .map(attribute_identifier)
+      .collect();
+    assert_eq!(names, vec!["x"]);
+  }
+
+  #[test]
+  fn unknown_base_is_dropped_under_skip() {
+    let definitions = parse_all(&["partial interface Ghost { attribute long a; };"]);
+    let merged = merge_partials_and_mixins(definitions, UnsupportedFeaturesArg::Skip).unwrap();
+    assert!(merged.is_empty());
+  }
+
+  #[test]
+  fn unknown_base_is_dropped_under_warn() {
+    let definitions = parse_all(&["partial interface Ghost { attribute long a; };"]);
+    let merged = merge_partials_and_mixins(definitions, UnsupportedFeaturesArg::Warn).unwrap();
+    assert!(merged.is_empty());
+  }
+
+  #[test]
+  fn unknown_base_is_rejected_under_bail() {
+    let definitions = parse_all(&["partial interface Ghost { attribute long a; };"]);
+    let err = merge_partials_and_mixins(definitions, UnsupportedFeaturesArg::Bail).unwrap_err();
+    assert!(err.to_string().contains("Ghost"));
+  }
+}